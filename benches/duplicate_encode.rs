@@ -0,0 +1,49 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use duplicate_encode::all_functions;
+use rand::distributions::Uniform;
+use rand::{prelude::StdRng, Rng, SeedableRng};
+use std::time::Duration;
+
+// Input sizes to benchmark each variant against, in characters.
+const INPUT_SIZES: [usize; 3] = [1_000, 100_000, 10_000_000];
+
+// Criterion's defaults (3s warmup, 100 samples) are tuned for benchmarks that
+// each take microseconds; our largest input size takes long enough per
+// iteration that the defaults would spend minutes per variant. Shrinking
+// both keeps a full run practical while still giving Criterion enough
+// samples to report a real mean/median with standard deviation.
+const WARM_UP_TIME: Duration = Duration::from_secs(1);
+const SAMPLE_SIZE: usize = 20;
+
+fn random_input(size: usize) -> String {
+    let random = StdRng::seed_from_u64(42);
+    let range = Uniform::new_inclusive(b'0', b'z');
+    random
+        .sample_iter(&range)
+        .take(size)
+        .map(char::from)
+        .collect()
+}
+
+// Registers every NamedFunction from the crate against every input size, so
+// Criterion can report mean/median with standard deviation and throughput
+// for each, distinguishing the near-identical variants at the noise floor
+// the old `Instant`-based harness couldn't resolve.
+fn bench_duplicate_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("duplicate_encode");
+    group.warm_up_time(WARM_UP_TIME);
+    group.sample_size(SAMPLE_SIZE);
+    for &size in INPUT_SIZES.iter() {
+        let input = random_input(size);
+        group.throughput(Throughput::Bytes(size as u64));
+        for f in all_functions() {
+            group.bench_with_input(BenchmarkId::new(f.name, size), &input, |b, input| {
+                b.iter(|| (f.body)(black_box(input)));
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_duplicate_encode);
+criterion_main!(benches);
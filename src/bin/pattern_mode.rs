@@ -0,0 +1,91 @@
+use rand::distributions::Uniform;
+use rand::{prelude::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::time::Instant;
+
+// Two strings "match" if there's a one-to-one character mapping making them
+// identical (e.g. "FOOFOO" and "BAABAA" both normalize to the same pattern).
+// Scans the bytes left to right, assigning 0 to the first distinct character
+// seen, 1 to the next new one, and so on. Two strings match iff their
+// pattern vectors are equal.
+fn pattern_encode(s: &str) -> Vec<u32> {
+    let mut first_seen = [-1i16; 256];
+    let mut next_id = 0u32;
+    let mut pattern = Vec::with_capacity(s.len());
+
+    for byte in s.as_bytes().iter() {
+        let index = &mut first_seen[*byte as usize];
+        if *index == -1 {
+            *index = next_id as i16;
+            next_id += 1;
+        }
+        pattern.push(*index as u32);
+    }
+    pattern
+}
+
+// Two strings match iff there's a one-to-one mapping between their
+// characters that turns one into the other.
+fn patterns_match(a: &str, b: &str) -> bool {
+    pattern_encode(a) == pattern_encode(b)
+}
+
+// Groups every word in `words` by its canonical pattern, and returns the
+// size of each group.
+fn group_by_pattern(words: &[String]) -> Vec<usize> {
+    let mut groups: HashMap<Vec<u32>, usize> = HashMap::new();
+    for word in words {
+        *groups.entry(pattern_encode(word)).or_default() += 1;
+    }
+    groups.into_values().collect()
+}
+
+fn report_groups(words: &[String]) {
+    let start = Instant::now();
+    let mut group_sizes = group_by_pattern(words);
+    let elapsed = start.elapsed();
+
+    group_sizes.sort_unstable_by(|a, b| b.cmp(a));
+    println!(
+        "Grouped {} words into {} pattern groups in {:.3} ms",
+        words.len(),
+        group_sizes.len(),
+        elapsed.as_secs_f64() * 1000.0
+    );
+    println!("Largest group sizes: {:?}", &group_sizes[..group_sizes.len().min(10)]);
+}
+
+// Generates `word_count` random words of `word_size` lowercase letters each,
+// from the same seeded generator the main benchmark uses for its input.
+fn generate_word_list(word_count: usize, word_size: usize) -> Vec<String> {
+    let mut random = StdRng::seed_from_u64(42);
+    let range = Uniform::new_inclusive(b'a', b'z');
+    (0..word_count)
+        .map(|_| {
+            (&mut random)
+                .sample_iter(&range)
+                .take(word_size)
+                .map(char::from)
+                .collect()
+        })
+        .collect()
+}
+
+fn main() {
+    assert_eq!(pattern_encode("din"), vec![0, 1, 2]);
+    assert!(patterns_match("FOOFOO", "BAABAA"));
+    assert!(!patterns_match("FOOFOO", "FOOBAR"));
+
+    let words = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(path)
+            .expect("could not read word list")
+            .split_whitespace()
+            .map(String::from)
+            .collect(),
+        None => generate_word_list(100_000, 8),
+    };
+
+    report_groups(&words);
+}
@@ -0,0 +1,151 @@
+use duplicate_encode::{NamedFunction, INPUT_WORD_NUM, INPUT_WORD_SIZE, TOTAL_INPUT_SIZE};
+use itertools::Itertools;
+use num_format::Locale;
+use num_format::ToFormattedString;
+use rand::distributions::Uniform;
+use rand::{prelude::StdRng, Rng, SeedableRng};
+use std::time::Instant;
+
+const NUMBER_OF_TEST_RUNS: usize = 10;
+
+// A second "count adjacent runs" kata alongside duplicate-encode: run-length
+// encoding. Emits each run as its character, preceded by the run length when
+// that length is greater than one (e.g. "aaabc" -> "3abc").
+
+// The obvious imperative version: walk the chars with a peekable iterator,
+// counting each run by hand.
+fn rle_naive(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut count = 1;
+        while chars.peek() == Some(&c) {
+            chars.next();
+            count += 1;
+        }
+        if count > 1 {
+            result.push_str(&count.to_string());
+        }
+        result.push(c);
+    }
+    result
+}
+
+// Similar to rle_naive, but avoiding a per-run to_string()/format!() call by
+// using itertools' `batching` to pull the next char and `take_while_ref` to
+// consume the rest of its run, then writing straight into the result buffer
+// with `format_with` instead of building an intermediate String per run.
+fn rle_batching(text: &str) -> String {
+    let chars = text.chars();
+    chars
+        .batching(|it| {
+            let c = it.next()?;
+            let mut count = 1;
+            it.take_while_ref(|&next| next == c).for_each(|_| count += 1);
+            Some((c, count))
+        })
+        .format_with("", |(c, count), f| {
+            if count > 1 {
+                f(&count)?;
+            }
+            f(&c)
+        })
+        .to_string()
+}
+
+// Similar to rle_batching, but grouping runs with itertools' `chunk_by`
+// instead of hand-rolling the run detection with `batching`.
+fn rle_group_by(text: &str) -> String {
+    text.chars()
+        .chunk_by(|&c| c)
+        .into_iter()
+        .map(|(c, group)| {
+            let count = group.count();
+            if count > 1 {
+                format!("{count}{c}")
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+fn test_functions(functions: &[NamedFunction]) {
+    println!("Running tests...");
+    let inputs_and_outputs = [
+        ("aaabccccd", "3ab4cd"),
+        ("abcd", "abcd"),
+        ("aaaa", "4a"),
+        ("", ""),
+    ];
+    for f in functions.iter() {
+        println!("Testing {}...", f.name);
+        for (input, expected) in inputs_and_outputs.iter() {
+            let actual = (f.body)(input);
+            assert_eq!(actual, expected.to_string(), "Wrong output for {}", f.name);
+        }
+    }
+    println!("All tests successfully passed.");
+}
+
+fn time_functions(functions: &[NamedFunction]) {
+    println!(
+        "Timing functions on {} characters per test (over {} tests)",
+        TOTAL_INPUT_SIZE.to_formatted_string(&Locale::en),
+        NUMBER_OF_TEST_RUNS
+    );
+
+    print!("Generating random input...");
+    let start = Instant::now();
+    let random = StdRng::seed_from_u64(42);
+    let range = Uniform::new_inclusive(b'0', b'z');
+    let input_word_chunk = random
+        .sample_iter(&range)
+        .take(INPUT_WORD_SIZE)
+        .map(char::from)
+        .collect::<String>()
+        .repeat(INPUT_WORD_NUM);
+    let input_word = input_word_chunk.as_str();
+    assert_eq!(input_word.len(), TOTAL_INPUT_SIZE);
+    println!(" took {} ms.", start.elapsed().as_millis());
+
+    let longest_name_len = functions.iter().map(|f| f.name.len()).max().unwrap();
+
+    for f in functions.iter() {
+        let start = Instant::now();
+        for _ in 0..NUMBER_OF_TEST_RUNS {
+            (f.body)(input_word);
+        }
+        let finish = start.elapsed();
+        println!(
+            "{:<max_len$} | {1:.3} seconds",
+            f.name,
+            finish.as_secs_f32(),
+            max_len = longest_name_len
+        );
+    }
+
+    println!(
+        "Took {0:.3} seconds in total.",
+        start.elapsed().as_secs_f32()
+    );
+}
+
+fn main() {
+    let functions = vec![
+        NamedFunction {
+            name: "rle_naive",
+            body: rle_naive,
+        },
+        NamedFunction {
+            name: "rle_batching",
+            body: rle_batching,
+        },
+        NamedFunction {
+            name: "rle_group_by",
+            body: rle_group_by,
+        },
+    ];
+    test_functions(&functions);
+    time_functions(&functions);
+}
@@ -0,0 +1,55 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+
+// Every variant in the library requires the whole string in memory, and
+// several even mutate it in place. This works in two passes over a
+// re-readable source instead: the first pass accumulates the `[u32; 256]`
+// byte histogram, then the source is rewound and the second pass re-reads
+// it, writing `(`/`)` bytes through a `BufWriter`. This lets multi-gigabyte
+// inputs be encoded in fixed memory, rather than requiring the whole input
+// to be loaded as a `String` up front.
+fn duplicate_encode_stream<R: Read + Seek, W: Write>(mut input: R, output: W) -> io::Result<()> {
+    const READ_BUFFER_SIZE: usize = 64 * 1024;
+    let mut buf = [0u8; READ_BUFFER_SIZE];
+    let mut counts = [0u32; 256];
+
+    loop {
+        let read = input.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        for byte in &buf[..read] {
+            counts[byte.to_ascii_lowercase() as usize] += 1;
+        }
+    }
+
+    input.seek(SeekFrom::Start(0))?;
+    let mut output = BufWriter::new(output);
+    loop {
+        let read = input.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        for byte in &mut buf[..read] {
+            let count = counts[byte.to_ascii_lowercase() as usize];
+            *byte = if count == 1 { b'(' } else { b')' };
+        }
+        output.write_all(&buf[..read])?;
+    }
+    output.flush()
+}
+
+fn main() {
+    let input = io::Cursor::new(b"din".to_vec());
+    let mut output = Vec::new();
+    duplicate_encode_stream(input, &mut output).unwrap();
+    assert_eq!(output, b"(((");
+
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => return,
+    };
+    let file = File::open(&path).expect("could not open input file");
+    let out = io::stdout();
+    duplicate_encode_stream(file, out.lock()).expect("stream encode failed");
+}
@@ -0,0 +1,413 @@
+use counter::Counter;
+use itertools::Itertools;
+use rayon::iter::ParallelIterator;
+use rayon::slice::{ParallelSlice, ParallelSliceMut};
+use std::collections::HashMap;
+
+pub const INPUT_WORD_SIZE: usize = 1_000_000;
+pub const INPUT_WORD_NUM: usize = 10;
+pub const CHUNK_COUNT: usize = 10_000;
+
+pub const TOTAL_INPUT_SIZE: usize = INPUT_WORD_SIZE * INPUT_WORD_NUM;
+pub const CHARS_PER_TEST: usize = TOTAL_INPUT_SIZE * INPUT_WORD_NUM;
+pub const CHUNK_SIZE: usize = TOTAL_INPUT_SIZE / CHUNK_COUNT;
+
+pub struct NamedFunction {
+    pub name: &'static str,
+    pub body: fn(&str) -> String,
+}
+
+// The initial implementation.
+#[allow(clippy::map_entry)]
+pub fn duplicate_encode(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    let mut counter: HashMap<char, usize> = HashMap::new();
+    for c in text.chars() {
+        if counter.contains_key(&c) {
+            *counter.get_mut(&c).unwrap() += 1;
+        } else {
+            counter.insert(c, 1);
+        }
+    }
+
+    let mut result = String::new();
+    for c in text.chars() {
+        let count = *counter.get(&c).unwrap();
+        result.push(if count == 1 { '(' } else { ')' });
+    }
+    result
+}
+
+// Similar to the duplicate_encode function, but using a different method of
+// populating the HashMap when counting elements at the beginning.
+// This method is noticably faster.
+pub fn duplicate_encode_default(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    let mut counter: HashMap<char, usize> = HashMap::new();
+    for c in text.chars() {
+        *counter.entry(c).or_default() += 1;
+    }
+
+    let mut result = String::new();
+    for c in text.chars() {
+        let count = *counter.get(&c).unwrap();
+        result.push(if count == 1 { '(' } else { ')' });
+    }
+    result
+}
+
+// Similar to duplicate_encode_better_insertion, but using String::with_capacity
+// instead of String::new to allocate space for the whole result once.
+// This seems to be slightly faster.
+pub fn duplicate_encode_capacity(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    let mut counter: HashMap<char, usize> = HashMap::new();
+    for c in text.chars() {
+        *counter.entry(c).or_default() += 1;
+    }
+
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        let count = *counter.get(&c).unwrap();
+        result.push(if count == 1 { '(' } else { ')' });
+    }
+    result
+}
+
+// Similar to duplicate_encode_capacity, but rather than converting the
+// lower string into a lowercase version upfront, converts individual chars into
+// lowercase as needed.
+// No noticable difference in performance.
+pub fn duplicate_encode_lower(text: &str) -> String {
+    let mut counter: HashMap<char, usize> = HashMap::new();
+    for c in text.chars() {
+        *counter.entry(c.to_ascii_lowercase()).or_default() += 1;
+    }
+
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        let count = *counter.get(&c.to_ascii_lowercase()).unwrap();
+        result.push(if count == 1 { '(' } else { ')' });
+    }
+    result
+}
+
+// Similar to duplicate_encode_capacity, but using Counter from the counter
+// crate to count elements, rather than using a HashMap.
+// This seems to be slower than the implementations that utilize a HashMap,
+// including the itertools version (which uses a HashMap internally)
+pub fn duplicate_encode_counter(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    let counts = text.chars().collect::<Counter<_>>();
+
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        let count = counts[&c];
+        result.push(if count == 1 { '(' } else { ')' });
+    }
+    result
+}
+
+// Similar to duplicate_encode_counter, but using itertools to count the elements.
+// Note that itertools also uses a HashMap.
+// This is faster than using a Counter, and provides similar performance to
+// the other versions that also use a HashMap.
+pub fn duplicate_encode_itertools(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    let counts = text.chars().counts();
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        let count = counts[&c];
+        result.push(if count == 1 { '(' } else { ')' });
+    }
+    result
+}
+
+// Similar to duplicate_encode_itertools, but using the map function to build
+// the final string using an iterator.
+// If anything, this is only slightly slower. This might be because the space
+// for the String built at the end is not allocated in advance.
+pub fn duplicate_encode_map(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    let counts = text.chars().counts();
+    text.chars()
+        .map(|c| if counts[&c] == 1 { '(' } else { ')' })
+        .collect()
+}
+
+// Similar to duplicate_encode_map, but iterating over bytes instead of chars.
+// Note that as_bytes() is used rather than bytes() to avoid copying the whole
+// input unnecessarily.
+// Slightly slower than duplicate_encode_map.
+pub fn duplicate_encode_bytes(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    let counts = text.as_bytes().iter().counts();
+    text.as_bytes()
+        .iter()
+        .map(|b| if counts[&b] == 1 { '(' } else { ')' })
+        .collect()
+}
+
+// Similar to duplicate_encode_bytes, but avoiding an extra allocation.
+// to_ascii_lowercase allocates space for a new String, and this method
+// updates this String in place before returning it.
+// Slightly slower than duplicate_encode_map, but faster than the
+// duplicate_encode_bytes function.
+pub fn duplicate_encode_in_place(text: &str) -> String {
+    let mut text = text.to_ascii_lowercase();
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for byte in text.as_bytes().iter() {
+        *counts.entry(*byte).or_default() += 1;
+    }
+    for byte in unsafe { text.as_bytes_mut() } {
+        *byte = if counts[byte] == 1 { b'(' } else { b')' };
+    }
+    text
+}
+
+// Similar to duplicate_encode_in_place, but using chunks.
+// No noticable difference in performance compared to duplicate_encode_in_place.
+pub fn duplicate_encode_chunks(text: &str) -> String {
+    let mut text = text.to_ascii_lowercase();
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for byte in text.as_bytes().iter() {
+        *counts.entry(*byte).or_default() += 1;
+    }
+
+    unsafe { text.as_bytes_mut() }
+        .chunks_mut(CHUNK_SIZE)
+        .for_each(|chunk| {
+            for byte in chunk {
+                *byte = if counts[byte] == 1 { b'(' } else { b')' };
+            }
+        });
+    text
+}
+
+// Similar to duplicate_encode_chunks, but by handling each chunk in parallel
+// using the "rayon" crate.
+// This is significantly faster than the other implementations.
+pub fn duplicate_encode_parallel(text: &str) -> String {
+    let mut text = text.to_ascii_lowercase();
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for byte in text.as_bytes().iter() {
+        *counts.entry(*byte).or_default() += 1;
+    }
+
+    unsafe { text.as_bytes_mut() }
+        .par_chunks_mut(CHUNK_SIZE)
+        .for_each(|chunk| {
+            for byte in chunk {
+                *byte = if counts[byte] == 1 { b'(' } else { b')' };
+            }
+        });
+    text
+}
+
+// Similar to duplicate_encode_parallel, but replacing the HashMap<u8, usize>
+// counter with a stack-allocated [u32; 256] table indexed directly by byte
+// value. Since the lowercased input is ASCII, this removes hashing and
+// allocation entirely and keeps the whole count table cache-resident.
+pub fn duplicate_encode_array(text: &str) -> String {
+    let mut text = text.to_ascii_lowercase();
+    let mut counts = [0u32; 256];
+    for byte in text.as_bytes().iter() {
+        counts[*byte as usize] += 1;
+    }
+
+    unsafe { text.as_bytes_mut() }
+        .par_chunks_mut(CHUNK_SIZE)
+        .for_each(|chunk| {
+            for byte in chunk {
+                *byte = if counts[*byte as usize] == 1 { b'(' } else { b')' };
+            }
+        });
+    text
+}
+
+// Similar to duplicate_encode_array, but also parallelizing the counting
+// phase instead of only the rewrite phase. Each chunk builds its own local
+// [u32; 256] table, and the tables are merged with an element-wise sum,
+// which is associative so chunk boundaries don't affect the result.
+pub fn duplicate_encode_parallel_count(text: &str) -> String {
+    let mut text = text.to_ascii_lowercase();
+    let counts = text
+        .as_bytes()
+        .par_chunks(CHUNK_SIZE)
+        .map(|chunk| {
+            let mut local = [0u32; 256];
+            for byte in chunk {
+                local[*byte as usize] += 1;
+            }
+            local
+        })
+        .reduce(
+            || [0u32; 256],
+            |mut a, b| {
+                for i in 0..256 {
+                    a[i] += b[i];
+                }
+                a
+            },
+        );
+
+    unsafe { text.as_bytes_mut() }
+        .par_chunks_mut(CHUNK_SIZE)
+        .for_each(|chunk| {
+            for byte in chunk {
+                *byte = if counts[*byte as usize] == 1 { b'(' } else { b')' };
+            }
+        });
+    text
+}
+
+// We never need the exact count of a byte, only whether it appears more than
+// once. This keeps two 256-bit bitsets ("seen" and "duplicated") in a single
+// pass: if a byte's "seen" bit is already set, its "duplicated" bit is set
+// too; otherwise its "seen" bit is set. The state fits in ~64 bytes, so it
+// stays resident in L1 for the whole run.
+pub fn duplicate_encode_bitmask(text: &str) -> String {
+    let mut text = text.to_ascii_lowercase();
+    let mut seen = [0u64; 4];
+    let mut duplicated = [0u64; 4];
+    for byte in text.as_bytes().iter() {
+        let byte = *byte as usize;
+        let (word, bit) = (byte / 64, 1u64 << (byte % 64));
+        if seen[word] & bit != 0 {
+            duplicated[word] |= bit;
+        } else {
+            seen[word] |= bit;
+        }
+    }
+
+    unsafe { text.as_bytes_mut() }
+        .par_chunks_mut(CHUNK_SIZE)
+        .for_each(|chunk| {
+            for byte in chunk {
+                let b = *byte as usize;
+                let (word, bit) = (b / 64, 1u64 << (b % 64));
+                *byte = if duplicated[word] & bit != 0 { b')' } else { b'(' };
+            }
+        });
+    text
+}
+
+// A single-threaded counterpart to duplicate_encode_array, and the baseline to
+// beat: the same stack-allocated [u32; 256] byte histogram, but rewriting the
+// string sequentially instead of through par_chunks_mut, so it's cheap to
+// measure the table lookup on its own without rayon's chunking overhead.
+pub fn duplicate_encode_table(text: &str) -> String {
+    let mut text = text.to_ascii_lowercase();
+    let mut counts = [0u32; 256];
+    for byte in text.as_bytes().iter() {
+        counts[*byte as usize] += 1;
+    }
+    for byte in unsafe { text.as_bytes_mut() } {
+        *byte = if counts[*byte as usize] == 1 { b'(' } else { b')' };
+    }
+    text
+}
+
+// The byte-oriented functions above ignore case folding for non-ASCII text
+// and treat each UTF-8 byte as its own character, so multi-byte scalar
+// values come out wrong. This iterates real `char`s, case-folds with
+// `char::to_lowercase` (taking its first yielded char, since a handful of
+// scalar values fold to more than one), counts into a `HashMap<char,
+// usize>`, and emits one `(`/`)` per scalar value rather than per byte.
+pub fn duplicate_encode_unicode(text: &str) -> String {
+    let folded: Vec<char> = text
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap())
+        .collect();
+
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for &c in &folded {
+        *counts.entry(c).or_default() += 1;
+    }
+
+    folded
+        .iter()
+        .map(|c| if counts[c] == 1 { '(' } else { ')' })
+        .collect()
+}
+
+// The byte-oriented functions (`duplicate_encode_bytes`, `_in_place`,
+// `_chunks`, `_parallel`, `_array`, `_parallel_count`, `_bitmask`,
+// `_table`) only handle ASCII correctly: a
+// multi-byte UTF-8 character is treated as several independent bytes, so
+// the output length no longer matches the character count for non-ASCII
+// input. Only `duplicate_encode_unicode` is exercised against these cases.
+pub const UNICODE_INPUTS_AND_OUTPUTS: [(&str, &str); 2] =
+    [("ÉÉé", ")))"), ("😀😀x", "))(")];
+
+// All of the variants above, in the order they were introduced, so binaries
+// (the CLI harness, Criterion benches) can iterate over them without having
+// to keep their own copy of the list in sync.
+pub fn all_functions() -> Vec<NamedFunction> {
+    vec![
+        NamedFunction {
+            name: "duplicate_encode",
+            body: duplicate_encode,
+        },
+        NamedFunction {
+            name: "duplicate_encode_default",
+            body: duplicate_encode_default,
+        },
+        NamedFunction {
+            name: "duplicate_encode_capacity",
+            body: duplicate_encode_capacity,
+        },
+        NamedFunction {
+            name: "duplicate_encode_lower",
+            body: duplicate_encode_lower,
+        },
+        NamedFunction {
+            name: "duplicate_encode_counter",
+            body: duplicate_encode_counter,
+        },
+        NamedFunction {
+            name: "duplicate_encode_itertools",
+            body: duplicate_encode_itertools,
+        },
+        NamedFunction {
+            name: "duplicate_encode_map",
+            body: duplicate_encode_map,
+        },
+        NamedFunction {
+            name: "duplicate_encode_bytes",
+            body: duplicate_encode_bytes,
+        },
+        NamedFunction {
+            name: "duplicate_encode_in_place",
+            body: duplicate_encode_in_place,
+        },
+        NamedFunction {
+            name: "duplicate_encode_chunks",
+            body: duplicate_encode_chunks,
+        },
+        NamedFunction {
+            name: "duplicate_encode_parallel",
+            body: duplicate_encode_parallel,
+        },
+        NamedFunction {
+            name: "duplicate_encode_array",
+            body: duplicate_encode_array,
+        },
+        NamedFunction {
+            name: "duplicate_encode_parallel_count",
+            body: duplicate_encode_parallel_count,
+        },
+        NamedFunction {
+            name: "duplicate_encode_bitmask",
+            body: duplicate_encode_bitmask,
+        },
+        NamedFunction {
+            name: "duplicate_encode_table",
+            body: duplicate_encode_table,
+        },
+        NamedFunction {
+            name: "duplicate_encode_unicode",
+            body: duplicate_encode_unicode,
+        },
+    ]
+}